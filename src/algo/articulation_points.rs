@@ -3,18 +3,98 @@ use std::cmp::min;
 use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 
+/// \[Generic\] Compute the [articulation points](https://en.wikipedia.org/wiki/Biconnected_component)
+/// (cut vertices) of a graph: the vertices whose removal increases its number of connected
+/// components. Delegates to [`biconnected_components`], keeping only its cut-vertex output.
+///
+/// # Breaking Changes
+/// Now that this shares `biconnected_components`'s DFS, it requires `G::EdgeId: Copy` in
+/// addition to its previous bounds (every graph type in this crate already satisfies this; only
+/// a custom `G` with a non-`Copy` `EdgeId` would stop compiling).
 pub fn articulation_points<G>(g: G) -> HashSet<G::NodeId>
 where
     G: IntoNodeReferences + IntoEdges + NodeIndexable,
     G::NodeWeight: Clone,
     G::EdgeWeight: Clone + PartialOrd,
     G::NodeId: Eq + Hash,
+    G::EdgeId: Copy,
+{
+    biconnected_components(g).0
+}
+
+/// \[Generic\] Compute the [biconnected components](https://en.wikipedia.org/wiki/Biconnected_component)
+/// of a graph, built on the same DFS as [`articulation_points`].
+///
+/// Alongside the set of cut vertices (the same set [`articulation_points`] returns), this also
+/// partitions the edges into maximal biconnected subgraphs: while the DFS runs, every traversed
+/// edge is pushed onto an edge stack, and whenever the tree-edge condition `low[child] >=
+/// disc[u]` fires (the point at which `u` is recognized as a cut vertex, or the DFS root's child
+/// returns), the edges accumulated since that child's tree edge are popped off as one component.
+///
+/// # Arguments
+/// * `g`: the graph to search.
+///
+/// # Returns
+/// * A set of the graph's cut vertices.
+/// * A partition of the graph's edges into biconnected components.
+///
+/// # Examples
+/// ```rust
+/// use petgraph::graph::UnGraph;
+/// use petgraph::algo::biconnected_components;
+///
+/// let mut g = UnGraph::<(), ()>::new_undirected();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// let c = g.add_node(());
+/// let d = g.add_node(());
+///
+/// // a and b are joined by two parallel edges, in addition to the triangle through c, and d
+/// // carries a self-loop.
+/// let ab1 = g.add_edge(a, b, ());
+/// let ab2 = g.add_edge(a, b, ());
+/// let bc = g.add_edge(b, c, ());
+/// let ca = g.add_edge(c, a, ());
+/// let cd = g.add_edge(c, d, ());
+/// let dd = g.add_edge(d, d, ());
+/// //     a
+/// //   // \
+/// //  b --- c --- d ⟲
+///
+/// let (ap, components) = biconnected_components(&g);
+/// assert_eq!(ap, [c].iter().cloned().collect()); // the self-loop doesn't make `d` a cut vertex
+///
+/// let mut component_edges: Vec<_> = components.iter().map(|comp| {
+///     let mut edges = comp.clone();
+///     edges.sort();
+///     edges
+/// }).collect();
+/// component_edges.sort();
+///
+/// let mut expected = vec![
+///     { let mut v = vec![ab1, ab2, bc, ca]; v.sort(); v },
+///     vec![cd],
+///     vec![dd], // the self-loop forms its own trivial one-edge component.
+/// ];
+/// expected.sort();
+///
+/// assert_eq!(component_edges, expected);
+/// ```
+pub fn biconnected_components<G>(g: G) -> (HashSet<G::NodeId>, Vec<Vec<G::EdgeId>>)
+where
+    G: IntoNodeReferences + IntoEdges + NodeIndexable,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone + PartialOrd,
+    G::NodeId: Eq + Hash,
+    G::EdgeId: Copy,
 {
     let mut visited = HashSet::with_capacity(g.node_references().size_hint().0);
     let mut parent = HashMap::with_capacity(g.node_references().size_hint().0);
     let mut low = HashMap::with_capacity(g.node_references().size_hint().0);
     let mut disc = HashMap::with_capacity(g.node_references().size_hint().0);
     let mut ap = HashSet::with_capacity(g.node_references().size_hint().0);
+    let mut edge_stack: Vec<G::EdgeId> = Vec::new();
+    let mut components: Vec<Vec<G::EdgeId>> = Vec::new();
     let mut time = 0;
 
     for node in g.node_references() {
@@ -28,14 +108,29 @@ where
                 &mut low,
                 &mut disc,
                 &mut ap,
+                &mut edge_stack,
+                &mut components,
                 &mut time,
             );
         }
     }
 
-    ap.into_iter().map(|id| g.from_index(id)).collect()
+    (
+        ap.into_iter().map(|id| g.from_index(id)).collect(),
+        components,
+    )
 }
 
+/// One level of the (explicit-stack-simulated) DFS call stack: the node currently being
+/// visited, its adjacency list materialized up front, and a cursor into it so neighbors are
+/// traversed one at a time, exactly like a recursive call would.
+struct Frame<E> {
+    node: usize,
+    neighbors: Vec<(usize, E)>,
+    pos: usize,
+}
+
+#[allow(clippy::too_many_arguments)]
 fn dfs<G>(
     g: &G,
     u: usize,
@@ -44,41 +139,93 @@ fn dfs<G>(
     low: &mut HashMap<usize, usize>,
     disc: &mut HashMap<usize, usize>,
     ap: &mut HashSet<usize>,
+    edge_stack: &mut Vec<G::EdgeId>,
+    components: &mut Vec<Vec<G::EdgeId>>,
     time: &mut usize,
 ) where
     G: IntoEdges + NodeIndexable,
+    G::EdgeId: Copy,
 {
-    let mut stack: Vec<(usize, Option<usize>)> = vec![(u, None)];
+    let neighbors_of = |g: &G, node: usize| -> Vec<(usize, G::EdgeId)> {
+        g.edges(g.from_index(node))
+            .map(|edge| (g.to_index(edge.target()), edge.id()))
+            .collect()
+    };
 
-    while let Some((current_node, maybe_current_child)) = stack.pop() {
-        if let Some(current_child) = maybe_current_child {
-            low.insert(current_node, min(low[&current_node], low[&current_child]));
+    let mut children: HashMap<usize, usize> = HashMap::new();
+    let mut tree_edge: HashMap<usize, G::EdgeId> = HashMap::new();
 
-            if parent.contains_key(&current_node) && low[&current_child] >= disc[&current_node] {
-                ap.insert(current_node);
-            }
-        } else {
-            visited.insert(current_node);
-            *time += 1;
-            disc.insert(current_node, *time);
-            low.insert(current_node, *time);
-            let mut children: usize = 0;
+    visited.insert(u);
+    *time += 1;
+    disc.insert(u, *time);
+    low.insert(u, *time);
+
+    let mut call_stack = vec![Frame {
+        node: u,
+        neighbors: neighbors_of(g, u),
+        pos: 0,
+    }];
+
+    while let Some(frame) = call_stack.last_mut() {
+        if frame.pos >= frame.neighbors.len() {
+            let finished = call_stack.pop().unwrap();
+            if let Some(&current_node) = parent.get(&finished.node) {
+                let low_child = low[&finished.node];
+                low.insert(current_node, min(low[&current_node], low_child));
 
-            for edge in g.edges(g.from_index(current_node)) {
-                let current_child = g.to_index(edge.target());
-                if !visited.contains(&current_child) {
-                    children += 1;
-                    parent.insert(current_child, current_node);
-                    stack.push((current_node, Some(current_child)));
-                    stack.push((current_child, None));
-                } else if current_child != parent.get(&current_node).cloned().unwrap_or(usize::MAX)
-                {
-                    low.insert(current_node, min(low[&current_node], disc[&current_child]));
+                if low_child >= disc[&current_node] {
+                    if parent.contains_key(&current_node) {
+                        ap.insert(current_node);
+                    }
+
+                    let finished_tree_edge = tree_edge[&finished.node];
+                    let mut component = vec![finished_tree_edge];
+                    while let Some(edge) = edge_stack.pop() {
+                        if edge == finished_tree_edge {
+                            break;
+                        }
+                        component.push(edge);
+                    }
+                    components.push(component);
                 }
+            } else if *children.get(&finished.node).unwrap_or(&0) > 1 {
+                ap.insert(finished.node);
             }
-            if parent.get(&current_node).is_none() && children > 1 {
-                ap.insert(current_node);
-            }
+            continue;
+        }
+
+        let current_node = frame.node;
+        let (current_child, edge_id) = frame.neighbors[frame.pos];
+        frame.pos += 1;
+
+        if !visited.contains(&current_child) {
+            visited.insert(current_child);
+            *time += 1;
+            disc.insert(current_child, *time);
+            low.insert(current_child, *time);
+            parent.insert(current_child, current_node);
+            tree_edge.insert(current_child, edge_id);
+            *children.entry(current_node).or_insert(0) += 1;
+            edge_stack.push(edge_id);
+
+            call_stack.push(Frame {
+                node: current_child,
+                neighbors: neighbors_of(g, current_child),
+                pos: 0,
+            });
+        } else if current_child == current_node {
+            // A self-loop connects to nothing else, so it forms its own trivial component.
+            components.push(vec![edge_id]);
+        } else if disc[&current_child] < disc[&current_node]
+            && tree_edge.get(&current_node) != Some(&edge_id)
+        {
+            // Only process this back edge from the descendant's side (the other endpoint will
+            // see it too, since adjacency is listed both ways for undirected graphs, but by then
+            // it's simply an edge to an already-fully-handled descendant). Comparing edge
+            // ids rather than `current_child` against the parent node keeps a parallel edge to
+            // the same parent from being mistaken for the (single) tree edge and dropped.
+            edge_stack.push(edge_id);
+            low.insert(current_node, min(low[&current_node], disc[&current_child]));
         }
     }
 }
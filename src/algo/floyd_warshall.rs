@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use std::hash::Hash;
 
+use crate::algo::shortest_path_tree::ShortestPathTree;
 use crate::algo::{BoundedMeasure, NegativeCycle};
 use crate::visit::{
     EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeCompactIndexable,
@@ -136,55 +137,90 @@ where
     Ok(distance_map)
 }
 
-fn path_from_shortest_path_tree<G>(
-    graph: G,
-    shortest_path_tree: &[Vec<Option<usize>>],
-    edge: (G::NodeId, G::NodeId),
-) -> Vec<(G::NodeId, G::NodeId)>
+/// An algorithm error: a cycle of negative weight was found in the graph, together with the
+/// vertices that make it up, in cyclic order.
+///
+/// # Examples
+/// ```rust
+/// use petgraph::{prelude::*, Graph, Directed};
+/// use petgraph::algo::floyd_warshall_path_tree;
+///
+/// let mut graph: Graph<(), i32, Directed> = Graph::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+///
+/// // a --1--> b --1--> c --(-5)--> a, a cycle of total weight -3.
+/// graph.extend_with_edges(&[(a, b, 1), (b, c, 1), (c, a, -5)]);
+///
+/// let err = floyd_warshall_path_tree(&graph, |edge| *edge.weight()).unwrap_err();
+/// assert_eq!(err.cycle(), &[b, c, a]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NegativeCycleWithPath<N>(pub(crate) Vec<N>);
+
+impl<N> NegativeCycleWithPath<N> {
+    /// Returns the vertices of the negative cycle, in cyclic order.
+    pub fn cycle(&self) -> &[N] {
+        &self.0
+    }
+}
+
+/// Reconstruct the vertices of one negative cycle using the predecessor matrix built while
+/// running Floyd–Warshall, given an `i` for which `dist[i][i] < 0`.
+///
+/// Walks `prev[i][·]` starting from `i` back towards `i`; since `prev[i][v]` holds the
+/// predecessor of `v` on the shortest known path from `i` to `v`, this retraces that path one
+/// hop at a time until a vertex repeats, at which point the repeated portion is the cycle.
+fn negative_cycle_vertices<G>(graph: G, prev: &[Vec<Option<usize>>], i: usize) -> Vec<G::NodeId>
 where
-    G: NodeCompactIndexable + IntoEdgeReferences + IntoNodeIdentifiers + GraphProp,
-    G::NodeId: Eq + Hash,
+    G: NodeCompactIndexable,
 {
-    let (source, target) = edge;
-    let u = graph.to_index(source);
-    let mut v = graph.to_index(target);
-    let mut v_id = target;
+    let mut chain = vec![i];
+    let mut on_chain = vec![false; prev.len()];
+    on_chain[i] = true;
 
-    if shortest_path_tree[u][v].is_none() {
-        return Vec::new();
-    }
-    let mut path = Vec::new();
-    while u != v {
-        if let Some(new_v) = shortest_path_tree[u][v] {
-            path.push((graph.from_index(new_v), v_id));
-            v = new_v;
-            v_id = graph.from_index(new_v);
+    let mut v = i;
+    loop {
+        v = prev[i][v].expect("dist[i][i] < 0 implies a path of predecessors back to i");
+        if on_chain[v] {
+            let start = chain.iter().position(|&node| node == v).unwrap();
+            return chain[start..]
+                .iter()
+                .rev()
+                .map(|&idx| graph.from_index(idx))
+                .collect();
         }
+        on_chain[v] = true;
+        chain.push(v);
     }
-
-    path.reverse();
-
-    path
 }
 
 #[allow(clippy::type_complexity, clippy::needless_range_loop)]
 /// \[Generic\] [Floyd–Warshall algorithm](https://en.wikipedia.org/wiki/Floyd%E2%80%93Warshall_algorithm) is an algorithm for all pairs shortest path problem
 ///
 /// Compute all pairs shortest paths in a weighted graph with positive or negative edge weights (but with no negative cycles).
-/// Returns HashMap of shortest path lengths. Additionally, returns HashMap of intermediate nodes along shortest path for indicated edges.
+/// Returns a hashmap of shortest path lengths, together with a [`ShortestPathTree`] that can
+/// reconstruct the vertices of the shortest path between any pair of vertices on demand.
 ///
 /// # Arguments
 /// * `graph`: graph with no negative cycle
 /// * `edge_cost`: closure that returns cost of a particular edge
 ///
 /// # Returns
-/// * `Ok`: (if graph contains no negative cycle) a hashmap containing all pairs shortest path distances and a hashmap for all pairs shortest paths
-/// * `Err`: if graph contains negative cycle.
+/// * `Ok`: (if graph contains no negative cycle) a hashmap containing all pairs shortest path distances and a [`ShortestPathTree`] for reconstructing paths
+/// * `Err`: a [`NegativeCycleWithPath`] carrying the vertices of an offending negative cycle, if graph contains one.
+///
+/// This is a newer entry point added alongside [`floyd_warshall_path`], which is kept rather
+/// than removed: it reports the offending cycle's vertices instead of a unit error, and returns a
+/// lazily-queryable [`ShortestPathTree`] instead of requiring the pairs of interest be declared
+/// up front. `floyd_warshall_path` itself is deprecated in favor of this one, but its signature
+/// hasn't changed, so existing callers aren't broken by this addition.
 ///
 /// # Examples
 /// ```rust
 /// use petgraph::{prelude::*, Graph, Directed};
-/// use petgraph::algo::floyd_warshall_path;
+/// use petgraph::algo::floyd_warshall_path_tree;
 /// use std::collections::HashMap;
 ///
 /// let mut graph: Graph<(), (), Directed> = Graph::new();
@@ -224,7 +260,7 @@ where
 /// ].iter().cloned().collect();
 ///
 ///
-/// let (res, paths) = floyd_warshall_path(&graph, Some([(a,c)].iter().cloned().collect()), |edge| {
+/// let (res, tree) = floyd_warshall_path_tree(&graph, |edge| {
 ///     if let Some(weight) = weight_map.get(&(edge.source(), edge.target())) {
 ///         *weight
 ///     } else {
@@ -232,7 +268,7 @@ where
 ///     }
 /// }).unwrap();
 ///
-/// assert_eq!(paths.get(&(a, c)), Some(vec![(a, b), (b, c)].as_ref()));
+/// assert_eq!(tree.path(a, c), Some(vec![a, b, c]));
 ///
 /// let nodes = [a, b, c, d];
 /// for node1 in &nodes {
@@ -242,20 +278,19 @@ where
 /// }
 ///
 /// ```
-pub fn floyd_warshall_path<G, F, K>(
+pub fn floyd_warshall_path_tree<G, F, K>(
     graph: G,
-    required_paths: Option<Vec<(G::NodeId, G::NodeId)>>,
     mut edge_cost: F,
 ) -> Result<
     (
         HashMap<(G::NodeId, G::NodeId), K>,
-        HashMap<(G::NodeId, G::NodeId), Vec<(G::NodeId, G::NodeId)>>,
+        ShortestPathTree<G::NodeId>,
     ),
-    NegativeCycle,
+    NegativeCycleWithPath<G::NodeId>,
 >
 where
     G: NodeCompactIndexable + IntoEdgeReferences + IntoNodeIdentifiers + GraphProp,
-    G::NodeId: Eq + Hash,
+    G::NodeId: Eq + Hash + Copy,
     F: FnMut(G::EdgeRef) -> K,
     K: BoundedMeasure + Copy,
 {
@@ -295,6 +330,16 @@ where
             }
         }
     }
+
+    // value less than 0(default value) indicates a negative cycle
+    for i in 0..num_of_nodes {
+        if dist[i][i] < K::default() {
+            return Err(NegativeCycleWithPath(negative_cycle_vertices(
+                graph, &prev, i,
+            )));
+        }
+    }
+
     let mut distance_map = HashMap::with_capacity(num_of_nodes * num_of_nodes);
 
     for i in 0..num_of_nodes {
@@ -303,10 +348,128 @@ where
         }
     }
 
+    let nodes: Vec<G::NodeId> = (0..num_of_nodes).map(|i| graph.from_index(i)).collect();
+
+    Ok((distance_map, ShortestPathTree::new(nodes, prev)))
+}
+
+#[allow(clippy::type_complexity)]
+/// \[Generic\] [Floyd–Warshall algorithm](https://en.wikipedia.org/wiki/Floyd%E2%80%93Warshall_algorithm) is an algorithm for all pairs shortest path problem
+///
+/// Compute all pairs shortest paths in a weighted graph with positive or negative edge weights (but with no negative cycles).
+/// Returns HashMap of shortest path lengths. Additionally, returns HashMap of intermediate nodes along shortest path for indicated edges.
+///
+/// # Arguments
+/// * `graph`: graph with no negative cycle
+/// * `required_paths`: pairs to reconstruct the path for, if any
+/// * `edge_cost`: closure that returns cost of a particular edge
+///
+/// # Returns
+/// * `Ok`: (if graph contains no negative cycle) a hashmap containing all pairs shortest path distances and a hashmap for all pairs shortest paths
+/// * `Err`: if graph contains negative cycle.
+///
+/// # Deprecated
+/// Superseded by [`floyd_warshall_path_tree`], which reports the offending negative cycle's
+/// vertices instead of a unit error and lets a path between any pair be reconstructed lazily
+/// instead of requiring `required_paths` up front. Kept with its original signature, implemented
+/// in terms of `floyd_warshall_path_tree`, so existing callers keep compiling.
+///
+/// # Examples
+/// ```rust
+/// #![allow(deprecated)]
+/// use petgraph::{prelude::*, Graph, Directed};
+/// use petgraph::algo::floyd_warshall_path;
+/// use std::collections::HashMap;
+///
+/// let mut graph: Graph<(), (), Directed> = Graph::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// let d = graph.add_node(());
+///
+/// graph.extend_with_edges(&[
+///    (a, b),
+///    (a, c),
+///    (a, d),
+///    (b, c),
+///    (b, d),
+///    (c, d)
+/// ]);
+///
+/// let weight_map: HashMap<(NodeIndex, NodeIndex), i32> = [
+///    ((a, a), 0), ((a, b), 1), ((a, c), 4), ((a, d), 10),
+///    ((b, b), 0), ((b, c), 2), ((b, d), 2),
+///    ((c, c), 0), ((c, d), 2)
+/// ].iter().cloned().collect();
+/// //     ----- b --------
+/// //    |      ^         | 2
+/// //    |    1 |    4    v
+/// //  2 |      a ------> c
+/// //    |   10 |         | 2
+/// //    |      v         v
+/// //     --->  d <-------
+///
+/// let (res, paths) = floyd_warshall_path(&graph, Some(vec![(a, c), (a, a)]), |edge| {
+///     if let Some(weight) = weight_map.get(&(edge.source(), edge.target())) {
+///         *weight
+///     } else {
+///         std::i32::MAX
+///     }
+/// }).unwrap();
+///
+/// assert_eq!(res.get(&(a, c)), Some(&3));
+/// assert_eq!(paths.get(&(a, c)), Some(vec![(a, b), (b, c)].as_ref()));
+/// // The same-node case has no intermediate hops, so its path is empty rather than absent.
+/// assert_eq!(paths.get(&(a, a)), Some(Vec::<(NodeIndex, NodeIndex)>::new().as_ref()));
+/// ```
+///
+/// A negative cycle is reported as an error, same as [`floyd_warshall`]:
+/// ```rust
+/// #![allow(deprecated)]
+/// use petgraph::{prelude::*, Graph, Directed};
+/// use petgraph::algo::floyd_warshall_path;
+///
+/// let mut graph: Graph<(), i32, Directed> = Graph::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+///
+/// // a --1--> b --1--> c --(-5)--> a, a cycle of total weight -3.
+/// graph.extend_with_edges(&[(a, b, 1), (b, c, 1), (c, a, -5)]);
+///
+/// assert!(floyd_warshall_path(&graph, None, |edge| *edge.weight()).is_err());
+/// ```
+#[deprecated(
+    note = "use `floyd_warshall_path_tree` instead, which reports the negative cycle's vertices and reconstructs paths lazily"
+)]
+pub fn floyd_warshall_path<G, F, K>(
+    graph: G,
+    required_paths: Option<Vec<(G::NodeId, G::NodeId)>>,
+    edge_cost: F,
+) -> Result<
+    (
+        HashMap<(G::NodeId, G::NodeId), K>,
+        HashMap<(G::NodeId, G::NodeId), Vec<(G::NodeId, G::NodeId)>>,
+    ),
+    NegativeCycle,
+>
+where
+    G: NodeCompactIndexable + IntoEdgeReferences + IntoNodeIdentifiers + GraphProp,
+    G::NodeId: Eq + Hash + Copy,
+    F: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy,
+{
+    let (distance_map, tree) =
+        floyd_warshall_path_tree(graph, edge_cost).map_err(|_| NegativeCycle(()))?;
+
     let mut path_map = HashMap::new();
     if let Some(edges) = required_paths {
-        for edge in edges {
-            path_map.insert(edge, path_from_shortest_path_tree(graph, &prev, edge));
+        for (source, target) in edges {
+            let path = tree
+                .path(source, target)
+                .map(|nodes| nodes.windows(2).map(|w| (w[0], w[1])).collect())
+                .unwrap_or_default();
+            path_map.insert((source, target), path);
         }
     }
 
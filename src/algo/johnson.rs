@@ -0,0 +1,351 @@
+use std::collections::{BinaryHeap, HashMap};
+
+use std::hash::Hash;
+use std::ops::Sub;
+
+use crate::algo::shortest_path_tree::ShortestPathTree;
+use crate::algo::{BoundedMeasure, NegativeCycle};
+use crate::scored::MinScored;
+use crate::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoEdges, IntoNodeIdentifiers, NodeCompactIndexable,
+};
+
+#[allow(clippy::type_complexity)]
+/// \[Generic\] [Johnson's algorithm](https://en.wikipedia.org/wiki/Johnson%27s_algorithm) is an algorithm for all pairs shortest path problem
+///
+/// Compute the length of each shortest path in a weighted graph with positive or negative edge
+/// weights (but with no negative cycles), running in O(|V|·|E|·log|V| + |V|²·log|V|) with this
+/// crate's binary-heap Dijkstra (a Fibonacci-heap Dijkstra would drop the `log|V|` factor on the
+/// `E` term, giving the textbook O(|V|·|E| + |V|²·log|V|), but this implementation doesn't use
+/// one). This still makes it a better fit than [`floyd_warshall`](crate::algo::floyd_warshall)
+/// for sparse graphs, since Floyd-Warshall always runs in Θ(|V|³) regardless of how many edges
+/// the graph has.
+///
+/// Internally, every edge is reweighted using a potential `h(v)` obtained from a Bellman-Ford run
+/// against a virtual source node with zero-weight edges to every vertex, which makes all
+/// reweighted edges non-negative; Dijkstra's algorithm is then run from every vertex over the
+/// reweighted graph, tracking predecessors along the way, and the true distances are recovered
+/// from its results.
+///
+/// # Arguments
+/// * `graph`: graph with no negative cycle
+/// * `edge_cost`: closure that returns cost of a particular edge
+///
+/// # Returns
+/// * `Ok`: (if graph contains no negative cycle) a hashmap containing all pairs shortest path distances and a [`ShortestPathTree`] for reconstructing paths
+/// * `Err`: if graph contains negative cycle.
+///
+/// # Examples
+/// ```rust
+/// use petgraph::{prelude::*, Graph, Directed};
+/// use petgraph::algo::johnson;
+/// use std::collections::HashMap;
+///
+/// let mut graph: Graph<(), (), Directed> = Graph::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// let d = graph.add_node(());
+///
+/// graph.extend_with_edges(&[
+///    (a, b),
+///    (a, c),
+///    (a, d),
+///    (b, c),
+///    (b, d),
+///    (c, d)
+/// ]);
+///
+/// let weight_map: HashMap<(NodeIndex, NodeIndex), i32> = [
+///    ((a, a), 0), ((a, b), 1), ((a, c), 4), ((a, d), 10),
+///    ((b, b), 0), ((b, c), 2), ((b, d), 2),
+///    ((c, c), 0), ((c, d), 2)
+/// ].iter().cloned().collect();
+/// //     ----- b --------
+/// //    |      ^         | 2
+/// //    |    1 |    4    v
+/// //  2 |      a ------> c
+/// //    |   10 |         | 2
+/// //    |      v         v
+/// //     --->  d <-------
+///
+/// let inf = std::i32::MAX;
+/// let expected_res: HashMap<(NodeIndex, NodeIndex), i32> = [
+///    ((a, a), 0), ((a, b), 1), ((a, c), 3), ((a, d), 3),
+///    ((b, a), inf), ((b, b), 0), ((b, c), 2), ((b, d), 2),
+///    ((c, a), inf), ((c, b), inf), ((c, c), 0), ((c, d), 2),
+///    ((d, a), inf), ((d, b), inf), ((d, c), inf), ((d, d), 0),
+/// ].iter().cloned().collect();
+///
+///
+/// let (res, tree) = johnson(&graph, |edge| {
+///     if let Some(weight) = weight_map.get(&(edge.source(), edge.target())) {
+///         *weight
+///     } else {
+///         inf
+///     }
+/// }).unwrap();
+///
+/// assert_eq!(tree.path(a, c), Some(vec![a, b, c]));
+/// assert_eq!(tree.path(a, a), Some(vec![a])); // same-node path is just the node itself
+/// assert_eq!(tree.path(b, a), None); // `b` can't reach `a`, so there's no path to reconstruct
+///
+/// let nodes = [a, b, c, d];
+/// for node1 in &nodes {
+///     for node2 in &nodes {
+///         assert_eq!(res.get(&(*node1, *node2)).unwrap(), expected_res.get(&(*node1, *node2)).unwrap());
+///     }
+/// }
+/// ```
+///
+/// Negative (but non-cycle-forming) edge weights are exactly what Johnson's reweighting step
+/// exists to handle, so they're exercised here against [`floyd_warshall`](crate::algo::floyd_warshall)'s
+/// answer on the same graph:
+/// ```rust
+/// use petgraph::{prelude::*, Graph, Directed};
+/// use petgraph::algo::{floyd_warshall, johnson};
+///
+/// let mut graph: Graph<(), i32, Directed> = Graph::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// let d = graph.add_node(());
+///
+/// // a --2--> b --(-1)--> c --(-2)--> d, plus a direct a --4--> d shortcut.
+/// graph.extend_with_edges(&[(a, b, 2), (b, c, -1), (c, d, -2), (a, d, 4)]);
+///
+/// let (johnson_res, tree) = johnson(&graph, |edge| *edge.weight()).unwrap();
+/// let floyd_warshall_res = floyd_warshall(&graph, |edge| *edge.weight()).unwrap();
+///
+/// for node1 in graph.node_identifiers() {
+///     for node2 in graph.node_identifiers() {
+///         assert_eq!(
+///             johnson_res.get(&(node1, node2)),
+///             floyd_warshall_res.get(&(node1, node2))
+///         );
+///     }
+/// }
+///
+/// // The reweighted Dijkstra still finds the path through the negative edges, not the
+/// // nominally-shorter-looking direct shortcut.
+/// assert_eq!(tree.path(a, d), Some(vec![a, b, c, d]));
+/// assert_eq!(johnson_res.get(&(a, d)), Some(&-1));
+/// ```
+///
+/// A negative cycle is reported as an error, just like [`floyd_warshall`](crate::algo::floyd_warshall):
+/// ```rust
+/// use petgraph::{prelude::*, Graph, Directed};
+/// use petgraph::algo::johnson;
+///
+/// let mut graph: Graph<(), i32, Directed> = Graph::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+///
+/// // a --1--> b --1--> c --(-5)--> a, a cycle of total weight -3.
+/// graph.extend_with_edges(&[(a, b, 1), (b, c, 1), (c, a, -5)]);
+///
+/// assert!(johnson(&graph, |edge| *edge.weight()).is_err());
+/// ```
+pub fn johnson<G, F, K>(
+    graph: G,
+    mut edge_cost: F,
+) -> Result<
+    (
+        HashMap<(G::NodeId, G::NodeId), K>,
+        ShortestPathTree<G::NodeId>,
+    ),
+    NegativeCycle,
+>
+where
+    G: NodeCompactIndexable + IntoEdgeReferences + IntoEdges + IntoNodeIdentifiers + GraphProp,
+    G::NodeId: Eq + Hash + Copy,
+    F: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy + Sub<K, Output = K>,
+{
+    let num_of_nodes = graph.node_count();
+    let potential = bellman_ford_potentials(graph, &mut edge_cost)?;
+
+    // |V|x|V| matrix, matching `floyd_warshall`'s output format: unreachable pairs are `K::max()`.
+    let mut distance_map: HashMap<(G::NodeId, G::NodeId), K> =
+        HashMap::with_capacity(num_of_nodes * num_of_nodes);
+    for i in 0..num_of_nodes {
+        for j in 0..num_of_nodes {
+            distance_map.insert((graph.from_index(i), graph.from_index(j)), K::max());
+        }
+    }
+
+    let mut prev: Vec<Vec<Option<usize>>> = Vec::with_capacity(num_of_nodes);
+
+    // Drive this by index, not `graph.node_identifiers()`: `prev`'s rows must line up with
+    // `to_index()` order (that's what `dijkstra_with_predecessors` indexes into and what the
+    // `ShortestPathTree` below assumes), and nothing guarantees `node_identifiers()` enumerates
+    // in that same order for an arbitrary `G`.
+    for source_index in 0..num_of_nodes {
+        let source = graph.from_index(source_index);
+        let h_source = potential[source_index];
+
+        // Reweight every edge as w'(u, v) = w(u, v) + h(u) - h(v), which Johnson's reweighting
+        // guarantees to be non-negative, so Dijkstra can be used from here on.
+        let reweighted_cost = |edge: G::EdgeRef| {
+            let (sum, overflow) =
+                edge_cost(edge).overflowing_add(potential[graph.to_index(edge.source())]);
+            if overflow {
+                // Treat an edge whose cost overflows (e.g. a `K::max()` "blocked" sentinel) as
+                // still blocked after reweighting, rather than silently wrapping into a bogus
+                // finite cost.
+                return K::max();
+            }
+            sub_or_max(sum, potential[graph.to_index(edge.target())])
+        };
+
+        let (reweighted_dist, source_prev) =
+            dijkstra_with_predecessors(graph, source, reweighted_cost);
+        for target in graph.node_identifiers() {
+            let target_index = graph.to_index(target);
+            if source_prev[target_index].is_none() {
+                continue;
+            }
+            let h_target = potential[target_index];
+            let recovered = sub_or_max(reweighted_dist[target_index], h_source);
+            let (recovered, overflow) = recovered.overflowing_add(h_target);
+            distance_map.insert((source, target), if overflow { K::max() } else { recovered });
+        }
+        prev.push(source_prev);
+    }
+
+    let nodes: Vec<G::NodeId> = (0..num_of_nodes).map(|i| graph.from_index(i)).collect();
+
+    Ok((distance_map, ShortestPathTree::new(nodes, prev)))
+}
+
+/// `a - b`, returning `K::max()` (the same "blocked"/unreachable sentinel used elsewhere in this
+/// module) instead of silently wrapping. `BoundedMeasure` has no `overflowing_sub`, so overflow
+/// is detected after the fact: subtracting `b` can only move `a` in the direction opposite `b`'s
+/// sign relative to `K::default()` (zero); if it moved the same direction instead, it wrapped.
+fn sub_or_max<K>(a: K, b: K) -> K
+where
+    K: BoundedMeasure + Copy + Sub<K, Output = K>,
+{
+    let diff = a - b;
+    let wrapped = if b >= K::default() { diff > a } else { diff < a };
+    if wrapped {
+        K::max()
+    } else {
+        diff
+    }
+}
+
+/// Dijkstra's algorithm rooted at `source`, additionally recording for every reached node the
+/// predecessor (by index) used to reach it along a shortest path, so that a [`ShortestPathTree`]
+/// can reconstruct paths after the fact. `source` itself is recorded as its own predecessor.
+fn dijkstra_with_predecessors<G, F, K>(
+    graph: G,
+    source: G::NodeId,
+    mut edge_cost: F,
+) -> (Vec<K>, Vec<Option<usize>>)
+where
+    G: NodeCompactIndexable + IntoEdges,
+    F: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy,
+{
+    let num_of_nodes = graph.node_count();
+    let mut dist = vec![K::max(); num_of_nodes];
+    let mut prev: Vec<Option<usize>> = vec![None; num_of_nodes];
+    let mut visited = vec![false; num_of_nodes];
+
+    let source_index = graph.to_index(source);
+    dist[source_index] = K::default();
+    prev[source_index] = Some(source_index);
+
+    let mut visit_next = BinaryHeap::new();
+    visit_next.push(MinScored(K::default(), source));
+
+    while let Some(MinScored(node_cost, node)) = visit_next.pop() {
+        let node_index = graph.to_index(node);
+        if visited[node_index] {
+            continue;
+        }
+        visited[node_index] = true;
+
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            let next_index = graph.to_index(next);
+            if visited[next_index] {
+                continue;
+            }
+            let (next_cost, overflow) = node_cost.overflowing_add(edge_cost(edge));
+            if !overflow && next_cost < dist[next_index] {
+                dist[next_index] = next_cost;
+                prev[next_index] = Some(node_index);
+                visit_next.push(MinScored(next_cost, next));
+            }
+        }
+    }
+
+    (dist, prev)
+}
+
+/// Compute the potential `h(v)` of every vertex, i.e. its shortest distance from a virtual source
+/// node `q` joined to every vertex of `graph` by a zero-weight edge. Since every such edge starts
+/// out at weight zero, this is equivalent to running Bellman-Ford with every vertex's initial
+/// distance set to zero, so `q` never needs to be materialized.
+///
+/// Returns `Err(NegativeCycle)` if `graph` contains a negative cycle.
+fn bellman_ford_potentials<G, F, K>(graph: G, edge_cost: &mut F) -> Result<Vec<K>, NegativeCycle>
+where
+    G: NodeCompactIndexable + IntoEdgeReferences + GraphProp,
+    F: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy,
+{
+    let num_of_nodes = graph.node_count();
+    let mut potential = vec![K::default(); num_of_nodes];
+
+    for _ in 1..num_of_nodes {
+        let mut relaxed = false;
+        for edge in graph.edge_references() {
+            relaxed |= relax(graph, &mut potential, edge, edge_cost);
+        }
+        if !relaxed {
+            break;
+        }
+    }
+
+    for edge in graph.edge_references() {
+        if relax(graph, &mut potential, edge, edge_cost) {
+            return Err(NegativeCycle(()));
+        }
+    }
+
+    Ok(potential)
+}
+
+/// Relax a single edge (and, for undirected graphs, its reverse direction) against `potential`,
+/// returning whether any distance was lowered.
+fn relax<G, F, K>(graph: G, potential: &mut [K], edge: G::EdgeRef, edge_cost: &mut F) -> bool
+where
+    G: NodeCompactIndexable + IntoEdgeReferences + GraphProp,
+    F: FnMut(G::EdgeRef) -> K,
+    K: BoundedMeasure + Copy,
+{
+    let u = graph.to_index(edge.source());
+    let v = graph.to_index(edge.target());
+    let cost = edge_cost(edge);
+    let mut relaxed = false;
+
+    let (result, overflow) = potential[u].overflowing_add(cost);
+    if !overflow && potential[v] > result {
+        potential[v] = result;
+        relaxed = true;
+    }
+
+    if !graph.is_directed() {
+        let (result, overflow) = potential[v].overflowing_add(cost);
+        if !overflow && potential[u] > result {
+            potential[u] = result;
+            relaxed = true;
+        }
+    }
+
+    relaxed
+}
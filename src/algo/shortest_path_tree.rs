@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// \[Generic\] The shortest-path predecessor tree produced by an all-pairs shortest path
+/// algorithm, such as [`floyd_warshall_path_tree`](crate::algo::floyd_warshall_path_tree) or
+/// [`johnson`](crate::algo::johnson).
+///
+/// Wraps the predecessor matrix built while computing all pairs' distances, and lets callers
+/// reconstruct the path between any pair of vertices lazily, via [`path`](Self::path), without
+/// having to declare the pairs of interest up front or rerun the underlying algorithm.
+#[derive(Clone, Debug)]
+pub struct ShortestPathTree<N> {
+    nodes: Vec<N>,
+    index_of: HashMap<N, usize>,
+    // `prev[source][target]` holds the penultimate vertex (by index into `nodes`) on the
+    // shortest known path from `source` to `target`, or `None` if `target` is unreached from
+    // `source`. `prev[source][source]` always holds `source` itself.
+    prev: Vec<Vec<Option<usize>>>,
+}
+
+impl<N> ShortestPathTree<N>
+where
+    N: Copy + Eq + Hash,
+{
+    pub(crate) fn new(nodes: Vec<N>, prev: Vec<Vec<Option<usize>>>) -> Self {
+        let index_of = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        ShortestPathTree {
+            nodes,
+            index_of,
+            prev,
+        }
+    }
+
+    /// Reconstructs the vertices of a shortest path from `source` to `target`, inclusive of both
+    /// endpoints, or `None` if `target` is unreachable from `source`.
+    pub fn path(&self, source: N, target: N) -> Option<Vec<N>> {
+        let u = *self.index_of.get(&source)?;
+        let mut v = *self.index_of.get(&target)?;
+        self.prev[u][v]?;
+
+        let mut path = vec![self.nodes[v]];
+        while v != u {
+            v = self.prev[u][v]
+                .expect("a reachable target always has a predecessor back to the source");
+            path.push(self.nodes[v]);
+        }
+        path.reverse();
+
+        Some(path)
+    }
+}